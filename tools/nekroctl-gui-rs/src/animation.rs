@@ -0,0 +1,79 @@
+//! Host-computed RGB animations that the firmware doesn't support natively.
+//!
+//! Each tick advances a phase counter and evaluates a per-zone color, which
+//! the caller streams to the keyboard via repeated `rgb per-zone` calls.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Animation {
+    /// Rainbow cycling across the four zones, offset by a fixed hue step.
+    RainbowCycle,
+    /// Smooth sweep between two base colors.
+    GradientSweep,
+    /// Brightness "breathing" pulse of a single base color.
+    Breathing,
+}
+
+impl Animation {
+    pub const ALL: [Animation; 3] = [Animation::RainbowCycle, Animation::GradientSweep, Animation::Breathing];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Animation::RainbowCycle => "Rainbow cycle",
+            Animation::GradientSweep => "Gradient sweep",
+            Animation::Breathing => "Breathing",
+        }
+    }
+
+    /// Evaluate the four per-zone hex colors and the brightness for this
+    /// animation at the given phase (0.0..1.0, wraps).
+    pub fn frame(&self, phase: f32, base: (u8, u8, u8), secondary: (u8, u8, u8)) -> ([String; 4], i32) {
+        match self {
+            Animation::RainbowCycle => {
+                let mut zones = [0; 4].map(|_| String::new());
+                for (i, zone) in zones.iter_mut().enumerate() {
+                    let hue = (phase + i as f32 * 0.25).fract();
+                    let (r, g, b) = hsv_to_rgb(hue, 1.0, 1.0);
+                    *zone = format!("{:02x}{:02x}{:02x}", r, g, b);
+                }
+                (zones, 100)
+            }
+            Animation::GradientSweep => {
+                // Triangle wave 0..1..0 so the sweep reverses smoothly instead of snapping.
+                let t = 1.0 - (phase.rem_euclid(1.0) * 2.0 - 1.0).abs();
+                let hex = lerp_hex(base, secondary, t);
+                (std::array::from_fn(|_| hex.clone()), 100)
+            }
+            Animation::Breathing => {
+                let t = (phase * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+                let brightness = (t * 100.0).round() as i32;
+                let hex = format!("{:02x}{:02x}{:02x}", base.0, base.1, base.2);
+                (std::array::from_fn(|_| hex.clone()), brightness.clamp(0, 100))
+            }
+        }
+    }
+}
+
+fn lerp_hex(a: (u8, u8, u8), b: (u8, u8, u8), t: f32) -> String {
+    let lerp = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    format!("{:02x}{:02x}{:02x}", lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+}
+
+/// `h`, `s`, `v` in 0.0..=1.0.
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
+    let i = (h * 6.0).floor();
+    let f = h * 6.0 - i;
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - f * s);
+    let t = v * (1.0 - (1.0 - f) * s);
+
+    let (r, g, b) = match (i as i32).rem_euclid(6) {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+
+    ((r * 255.0).round() as u8, (g * 255.0).round() as u8, (b * 255.0).round() as u8)
+}
@@ -0,0 +1,205 @@
+//! Persistent configuration: named presets and startup flags.
+//!
+//! Stored as TOML under the XDG config dir (`~/.config/nekro-sense/config.toml`).
+//! Missing or partially-filled files are tolerated; any field left out falls
+//! back to its `Default`.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    #[serde(default = "Preset::default_colors")]
+    pub kb_colors: [String; 4],
+    #[serde(default = "Preset::default_brightness")]
+    pub kb_brightness: i32,
+    #[serde(default = "Preset::default_effect_mode")]
+    pub kb_effect_mode: String,
+    #[serde(default)]
+    pub logo_color: String,
+    #[serde(default)]
+    pub power_current: String,
+    #[serde(default = "Preset::default_fan_val")]
+    pub fans_cpu_val: i32,
+    #[serde(default = "Preset::default_fan_val")]
+    pub fans_gpu_val: i32,
+}
+
+impl Preset {
+    fn default_colors() -> [String; 4] {
+        [
+            "00aaff".to_string(),
+            "00aaff".to_string(),
+            "00aaff".to_string(),
+            "00aaff".to_string(),
+        ]
+    }
+
+    fn default_brightness() -> i32 {
+        100
+    }
+
+    fn default_effect_mode() -> String {
+        "wave".to_string()
+    }
+
+    fn default_fan_val() -> i32 {
+        50
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Flags {
+    #[serde(default = "Flags::default_startup_page")]
+    pub startup_page: String,
+    #[serde(default)]
+    pub auto_apply_preset: Option<String>,
+    #[serde(default = "Flags::default_theme")]
+    pub theme: String,
+    /// Pin a specific escalation backend ("sudo", "sudo-rs", "doas", "pkexec")
+    /// instead of trying them in default priority order.
+    #[serde(default)]
+    pub escalation_backend: Option<String>,
+}
+
+impl Flags {
+    fn default_startup_page() -> String {
+        "keyboard".to_string()
+    }
+
+    fn default_theme() -> String {
+        "Dark".to_string()
+    }
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self {
+            startup_page: Flags::default_startup_page(),
+            auto_apply_preset: None,
+            theme: Flags::default_theme(),
+            escalation_backend: None,
+        }
+    }
+}
+
+/// Keyboard shortcuts, expressed as the names `key_from_name` understands
+/// ("1".."9", letters, "Enter", "Tab", ...). Configurable so users on
+/// terminals/layouts with remapped keys can override them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bindings {
+    #[serde(default = "Bindings::default_page_keyboard")]
+    pub page_keyboard: String,
+    #[serde(default = "Bindings::default_page_power")]
+    pub page_power: String,
+    #[serde(default = "Bindings::default_page_fans")]
+    pub page_fans: String,
+    #[serde(default = "Bindings::default_refresh")]
+    pub refresh: String,
+    #[serde(default = "Bindings::default_apply")]
+    pub apply: String,
+}
+
+impl Bindings {
+    fn default_page_keyboard() -> String { "1".to_string() }
+    fn default_page_power() -> String { "2".to_string() }
+    fn default_page_fans() -> String { "3".to_string() }
+    fn default_refresh() -> String { "R".to_string() }
+    fn default_apply() -> String { "Enter".to_string() }
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self {
+            page_keyboard: Bindings::default_page_keyboard(),
+            page_power: Bindings::default_page_power(),
+            page_fans: Bindings::default_page_fans(),
+            refresh: Bindings::default_refresh(),
+            apply: Bindings::default_apply(),
+        }
+    }
+}
+
+/// Parse a binding name (as stored in the config file) into an egui key.
+pub fn key_from_name(name: &str) -> Option<eframe::egui::Key> {
+    use eframe::egui::Key;
+    match name {
+        "0" => Some(Key::Num0),
+        "1" => Some(Key::Num1),
+        "2" => Some(Key::Num2),
+        "3" => Some(Key::Num3),
+        "4" => Some(Key::Num4),
+        "5" => Some(Key::Num5),
+        "6" => Some(Key::Num6),
+        "7" => Some(Key::Num7),
+        "8" => Some(Key::Num8),
+        "9" => Some(Key::Num9),
+        "Enter" => Some(Key::Enter),
+        "Tab" => Some(Key::Tab),
+        "Escape" => Some(Key::Escape),
+        "Space" => Some(Key::Space),
+        other if other.len() == 1 => {
+            let c = other.chars().next()?.to_ascii_uppercase();
+            Key::from_name(&c.to_string())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub flags: Flags,
+    #[serde(default)]
+    pub bindings: Bindings,
+    #[serde(default, rename = "preset")]
+    pub presets: Vec<Preset>,
+}
+
+impl Config {
+    /// Load the config file, falling back to defaults if it is missing,
+    /// unreadable, or only partially valid TOML.
+    pub fn load() -> Self {
+        let path = match Self::path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = match Self::path() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = toml::to_string_pretty(self).unwrap_or_default();
+        std::fs::write(path, contents)
+    }
+
+    pub fn preset(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|p| p.name == name)
+    }
+
+    pub fn upsert_preset(&mut self, preset: Preset) {
+        if let Some(existing) = self.presets.iter_mut().find(|p| p.name == preset.name) {
+            *existing = preset;
+        } else {
+            self.presets.push(preset);
+        }
+    }
+
+    pub fn remove_preset(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("nekro-sense").join("config.toml"))
+    }
+}
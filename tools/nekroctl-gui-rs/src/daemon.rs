@@ -0,0 +1,137 @@
+//! Long-lived privileged helper: escalate once, then talk to a daemon over a
+//! Unix domain socket instead of paying the full escalation cost (direct,
+//! `sudo -n`, interactive password, `pkexec`) on every privileged call.
+//!
+//! The daemon side is the privileged Python process, launched once with
+//! `--daemon --socket <path>` through the normal [`crate::escalation`]
+//! backends; it owns the authenticated root session and answers requests
+//! until it's been idle past its own heartbeat timeout. If the daemon can't
+//! be reached or spawned, callers should fall back to the one-shot
+//! [`crate::run_privileged`] path.
+//!
+//! Not every `nekroctl.py` this GUI might be pointed at implements this
+//! protocol, so [`spawn`] checks the script's own `--help` output for
+//! `--daemon` support before attempting escalation - see `is_supported`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const ARG_SEP: char = '\u{1f}';
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const SPAWN_WAIT: Duration = Duration::from_secs(5);
+
+/// Whether `script_path`'s own (unprivileged, no escalation needed) `--help`
+/// output advertises `--daemon` support. The Rust side speaks a bespoke
+/// `--daemon --socket <path>` protocol that only a nekroctl.py built for it
+/// understands; without this check, every privileged call against a script
+/// that doesn't implement it would still pay for a failed escalation attempt
+/// and the `spawn_noninteractive` grace-period probe before falling back to
+/// the one-shot path. Cached for the process lifetime since the answer can't
+/// change without a restart.
+fn is_supported(python_path: &str, script_path: &str) -> bool {
+    static SUPPORTED: OnceLock<bool> = OnceLock::new();
+    *SUPPORTED.get_or_init(|| {
+        Command::new(python_path)
+            .args([script_path, "--help"])
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).contains("--daemon"))
+            .unwrap_or(false)
+    })
+}
+
+pub fn socket_path() -> PathBuf {
+    let runtime_dir = std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| "/tmp".to_string());
+    PathBuf::from(runtime_dir).join("nekro-sense.sock")
+}
+
+fn connect() -> Option<UnixStream> {
+    let stream = UnixStream::connect(socket_path()).ok()?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(CONNECT_TIMEOUT)).ok()?;
+
+    // Only trust a socket whose peer is root - otherwise something else could
+    // be squatting on the path and we'd be feeding it privileged requests.
+    let cred = stream.peer_cred().ok()?;
+    if cred.uid() != 0 {
+        return None;
+    }
+
+    Some(stream)
+}
+
+pub fn is_running() -> bool {
+    connect().is_some()
+}
+
+/// Escalate once (via the normal backend chain) to launch the daemon in the
+/// background, then wait for its socket to appear.
+pub fn spawn(python_path: &str, script_path: &str, pinned_backend: Option<&str>) -> Result<(), String> {
+    if !is_supported(python_path, script_path) {
+        return Err("this nekroctl.py doesn't support --daemon mode".to_string());
+    }
+
+    let socket = socket_path();
+    let daemon_args = vec![
+        script_path.to_string(),
+        "--daemon".to_string(),
+        "--socket".to_string(),
+        socket.to_string_lossy().to_string(),
+    ];
+
+    // The daemon never exits on its own, so it can't be launched through the
+    // one-shot `try_noninteractive`/`try_interactive` paths - those block on
+    // `Command::output()` until the child exits. `spawn_noninteractive` spawns
+    // and detaches instead, and we confirm success by waiting for the socket
+    // rather than for the process to finish.
+    for backend in crate::escalation::ordered_backends(pinned_backend) {
+        if backend.spawn_noninteractive(python_path, &daemon_args).is_some() {
+            return wait_for_socket(&socket);
+        }
+    }
+
+    Err("no escalation backend could start the daemon without a password prompt".to_string())
+}
+
+fn wait_for_socket(socket: &std::path::Path) -> Result<(), String> {
+    let deadline = std::time::Instant::now() + SPAWN_WAIT;
+    while std::time::Instant::now() < deadline {
+        if socket.exists() && is_running() {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Err("daemon did not come up in time".to_string())
+}
+
+/// Send a request to the running daemon. Returns `None` if the daemon isn't
+/// reachable at all (stale/missing socket) so the caller can respawn it or
+/// fall back to the one-shot escalation path; a request the daemon itself
+/// rejects still comes back as `Some((false, message))`.
+pub fn send_request(args: &[String]) -> Option<(bool, String)> {
+    let mut stream = connect()?;
+
+    let line = args.join(&ARG_SEP.to_string());
+    stream.write_all(line.as_bytes()).ok()?;
+    stream.write_all(b"\n").ok()?;
+
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply).ok()?;
+    let reply = reply.trim_end();
+
+    match reply.split_once(' ') {
+        Some(("OK", rest)) => Some((true, rest.to_string())),
+        Some(("ERR", rest)) => Some((false, rest.to_string())),
+        _ => Some((false, "malformed daemon response".to_string())),
+    }
+}
+
+/// Ask the daemon to exit gracefully instead of waiting out its idle timeout.
+pub fn shutdown() {
+    if let Some(mut stream) = connect() {
+        let _ = stream.write_all(b"__shutdown__\n");
+    }
+}
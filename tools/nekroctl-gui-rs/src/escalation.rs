@@ -0,0 +1,419 @@
+//! Pluggable privilege-escalation backends.
+//!
+//! The three-step fallback used to be hard-coded to GNU `sudo` and `pkexec`,
+//! with stderr-matching heuristics that break under alternative
+//! implementations. Each backend here knows its own argument layout and its
+//! own set of permission-denied / password-required signals, so the crate
+//! also works on minimal/hardened systems that ship `doas` or the Rust
+//! `sudo-rs` instead of GNU sudo.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+pub enum EscalationResult {
+    /// The backend ran to completion (successfully or not); nothing else to try.
+    Done(bool, String),
+    /// The backend reports that interactive authentication is required.
+    /// The caller should offer `try_interactive` before moving to the next backend.
+    NeedsPassword,
+}
+
+pub trait Escalator {
+    fn name(&self) -> &'static str;
+
+    /// Whether this backend's binary is present (and, for backends that
+    /// share a binary name with another implementation, whether the
+    /// installed binary actually matches this one's variant).
+    fn is_available(&self) -> bool;
+
+    /// Attempt escalation without any interactive prompt (e.g. `sudo -n`,
+    /// `doas -n`, bare `pkexec`).
+    fn try_noninteractive(&self, python_path: &str, full_args: &[String]) -> EscalationResult;
+
+    /// Retry with a password the caller already collected - over a TTY in
+    /// CLI mode, or through a GUI prompt when running without one (see the
+    /// `PasswordRequest` channel in main.rs). Only backends that can
+    /// accept a password over stdin support this; others return `None` so
+    /// the caller falls through to the next backend in priority order.
+    fn try_interactive(&self, _python_path: &str, _full_args: &[String], _password: &str) -> Option<(bool, String)> {
+        None
+    }
+
+    /// Like `try_noninteractive`, but for launching a process that's expected
+    /// to keep running (the privileged daemon) rather than exit - spawns
+    /// without waiting, so the caller isn't blocked on a child that never
+    /// terminates. Returns `None` if the backend can't spawn it (missing
+    /// binary, or it requires interactive auth); [`crate::daemon::spawn`] then
+    /// tries the next backend, and returns an error once none can spawn it,
+    /// so its caller falls back to one-shot escalation.
+    fn spawn_noninteractive(&self, _python_path: &str, _full_args: &[String]) -> Option<()> {
+        None
+    }
+}
+
+/// Environment variables the privileged Python process is allowed to see
+/// beyond whatever the escalation backend keeps by default.
+///
+/// `sudo`/`sudo-rs` use `--preserve-env=<list>` for this (see
+/// `sudo_preserve_env_args`), which keeps the exec target as the interpreter
+/// itself - important because a passwordless sudoers rule scoped to
+/// `NOPASSWD: /usr/bin/python3 …/nekroctl.py *` matches on the *invoked*
+/// command, and wrapping it in `env -i ...` would change that to `/usr/bin/env`
+/// and silently break the rule. `doas` has no such preserve-specific-vars
+/// flag, so it's the one backend that still goes through `env -i` (see
+/// `env_wrapped`). `pkexec` needs neither: it already resets the environment
+/// to its own deterministic default, and (per `crate::polkit`) keeping its
+/// exec target as the interpreter is what lets us cache authorization safely.
+pub const ALLOWED_ENV_VARS: &[&str] = &["PATH", "LANG", "LC_ALL", "LANGUAGE", "PYTHONPATH", "VIRTUAL_ENV"];
+
+/// Build `--preserve-env=<list>` for `sudo`/`sudo-rs`, so the exec target
+/// sudoers sees is still `<python_path>`, not a generic wrapper binary.
+fn sudo_preserve_env_args(python_path: &str, full_args: &[String]) -> Vec<String> {
+    let mut command = vec![format!("--preserve-env={}", ALLOWED_ENV_VARS.join(","))];
+    command.push(python_path.to_string());
+    command.extend(full_args.iter().cloned());
+    command
+}
+
+/// Build `env -i VAR=val ... <python_path> <args...>` for `doas`, which has
+/// no equivalent to `sudo --preserve-env=<list>`.
+fn env_wrapped(python_path: &str, full_args: &[String]) -> Vec<String> {
+    let mut command = vec!["env".to_string(), "-i".to_string()];
+    for var in ALLOWED_ENV_VARS {
+        if let Ok(val) = std::env::var(var) {
+            command.push(format!("{var}={val}"));
+        }
+    }
+    command.push(python_path.to_string());
+    command.extend(full_args.iter().cloned());
+    command
+}
+
+fn binary_exists(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// sudo-rs identifies itself in `sudo --version`; GNU sudo does not mention it.
+fn is_sudo_rs() -> bool {
+    Command::new("sudo")
+        .arg("--version")
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).to_lowercase().contains("sudo-rs"))
+        .unwrap_or(false)
+}
+
+/// Strip a leading `[sudo] password for <user>: ` prompt that sudo/sudo-rs
+/// echo into the combined output when run with a pty-less stdin.
+fn strip_password_prompt(stderr: &str) -> String {
+    if let Some(idx) = stderr.find("password for") {
+        if let Some(colon_offset) = stderr[idx..].find(':') {
+            return stderr[idx + colon_offset + 1..].trim_start().to_string();
+        }
+    }
+    stderr.trim().to_string()
+}
+
+/// Prompt for a password on the controlling TTY, for CLI invocations. Returns
+/// `None` if there isn't one (e.g. launched from a desktop menu with stdin
+/// closed) - callers should fall back to collecting the password through a
+/// GUI dialog instead rather than silently skipping this backend.
+pub fn prompt_tty_password(binary: &str) -> Option<String> {
+    use std::io::IsTerminal;
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+    rpassword::prompt_password(format!("[{binary}] password: ")).ok()
+}
+
+/// Run `<binary> -S <prompt_flag> "" <command...>`, feeding `password` over
+/// stdin. Shared by every backend's `try_interactive`, whichever way the
+/// password was collected (TTY prompt or GUI dialog).
+fn run_with_password(binary: &str, prompt_flag: &str, command: &[String], password: &str) -> Option<(bool, String)> {
+    use zeroize::Zeroize;
+
+    let mut password = password.to_string();
+
+    let mut child = Command::new(binary)
+        .arg("-S")
+        .arg(prompt_flag)
+        .arg("")
+        .args(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = writeln!(stdin, "{password}");
+    }
+    password.zeroize();
+
+    let output = child.wait_with_output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = strip_password_prompt(&String::from_utf8_lossy(&output.stderr));
+
+    if output.status.success() {
+        Some((true, stdout))
+    } else if stderr.to_lowercase().contains("sorry, try again")
+        || stderr.to_lowercase().contains("incorrect")
+        || stderr.to_lowercase().contains("authentication failure")
+    {
+        // Only one attempt is ever offered - treat a rejected password as a
+        // hard failure instead of looping back to prompt a second time.
+        Some((false, "authentication failed: incorrect password".to_string()))
+    } else {
+        Some((false, stderr))
+    }
+}
+
+/// `sudo -n --preserve-env=... <python_path> <args...>`, shared by the
+/// GNU-sudo and sudo-rs backends - they only differ in how they recognize a
+/// password-required failure.
+fn sudo_noninteractive(python_path: &str, full_args: &[String]) -> std::io::Result<std::process::Output> {
+    let mut args = vec!["-n".to_string()];
+    args.extend(sudo_preserve_env_args(python_path, full_args));
+    Command::new("sudo").args(&args).output()
+}
+
+/// How long to give a just-spawned child to fail fast (no cached
+/// authorization, a rejected password, a dismissed pkexec dialog) before we
+/// commit to it as the daemon. A successful daemon never exits on its own,
+/// so we can't wait for that; but most non-interactive auth failures surface
+/// within milliseconds, well under this window.
+const SPAWN_FAIL_GRACE: Duration = Duration::from_millis(300);
+
+/// Spawn-without-waiting for a process expected to keep running, used by
+/// every backend's `spawn_noninteractive`. A bare `Command::spawn()`
+/// succeeding only proves the binary launched, not that it authenticated -
+/// `sudo -n`/`doas -n` exit immediately (well within this grace period) if no
+/// authorization is cached, and a dismissed pkexec dialog exits just as
+/// fast. Give it `SPAWN_FAIL_GRACE` to fail before reaping it on a
+/// background thread so a real detached daemon is never waited on.
+fn spawn_detached(mut child: std::process::Child) -> Option<()> {
+    std::thread::sleep(SPAWN_FAIL_GRACE);
+    match child.try_wait() {
+        Ok(Some(status)) if !status.success() => None,
+        Ok(_) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+            Some(())
+        }
+        Err(_) => None,
+    }
+}
+
+/// `sudo -n --preserve-env=... <python_path> <args...>`, spawned without
+/// waiting for it to exit - see `Escalator::spawn_noninteractive`.
+fn sudo_spawn_noninteractive(python_path: &str, full_args: &[String]) -> Option<()> {
+    let mut args = vec!["-n".to_string()];
+    args.extend(sudo_preserve_env_args(python_path, full_args));
+    spawn_detached(Command::new("sudo").args(&args).spawn().ok()?)
+}
+
+pub struct GnuSudo;
+
+impl Escalator for GnuSudo {
+    fn name(&self) -> &'static str {
+        "sudo"
+    }
+
+    fn is_available(&self) -> bool {
+        binary_exists("sudo") && !is_sudo_rs()
+    }
+
+    fn try_noninteractive(&self, python_path: &str, full_args: &[String]) -> EscalationResult {
+        let output = sudo_noninteractive(python_path, full_args);
+
+        match output {
+            Ok(out) if out.status.success() => {
+                EscalationResult::Done(true, String::from_utf8_lossy(&out.stdout).trim().to_string())
+            }
+            Ok(out) => {
+                let err = String::from_utf8_lossy(&out.stderr).to_lowercase();
+                let needs_password = out.status.code() == Some(127)
+                    || err.contains("a password is required")
+                    || (err.contains("password") && (err.contains("authentication") || err.contains("is required")))
+                    || err.contains("no tty present")
+                    || err.contains("unable to authenticate");
+
+                if needs_password {
+                    EscalationResult::NeedsPassword
+                } else {
+                    EscalationResult::Done(false, String::from_utf8_lossy(&out.stderr).trim().to_string())
+                }
+            }
+            Err(e) => EscalationResult::Done(false, e.to_string()),
+        }
+    }
+
+    fn try_interactive(&self, python_path: &str, full_args: &[String], password: &str) -> Option<(bool, String)> {
+        run_with_password("sudo", "-p", &sudo_preserve_env_args(python_path, full_args), password)
+    }
+
+    fn spawn_noninteractive(&self, python_path: &str, full_args: &[String]) -> Option<()> {
+        sudo_spawn_noninteractive(python_path, full_args)
+    }
+}
+
+/// `sudo-rs`'s wording differs from GNU sudo's ("Sorry, try again." /
+/// "incorrect authentication" rather than "a password is required"), and it
+/// notably does not preserve arbitrary env vars the way GNU sudo with
+/// `SETENV` can - relevant once the caller needs a deterministic environment.
+pub struct SudoRs;
+
+impl Escalator for SudoRs {
+    fn name(&self) -> &'static str {
+        "sudo-rs"
+    }
+
+    fn is_available(&self) -> bool {
+        binary_exists("sudo") && is_sudo_rs()
+    }
+
+    fn try_noninteractive(&self, python_path: &str, full_args: &[String]) -> EscalationResult {
+        let output = sudo_noninteractive(python_path, full_args);
+
+        match output {
+            Ok(out) if out.status.success() => {
+                EscalationResult::Done(true, String::from_utf8_lossy(&out.stdout).trim().to_string())
+            }
+            Ok(out) => {
+                let err = String::from_utf8_lossy(&out.stderr).to_lowercase();
+                let needs_password = err.contains("authentication required")
+                    || err.contains("interactive authentication")
+                    || err.contains("a terminal is required");
+
+                if needs_password {
+                    EscalationResult::NeedsPassword
+                } else {
+                    EscalationResult::Done(false, String::from_utf8_lossy(&out.stderr).trim().to_string())
+                }
+            }
+            Err(e) => EscalationResult::Done(false, e.to_string()),
+        }
+    }
+
+    fn try_interactive(&self, python_path: &str, full_args: &[String], password: &str) -> Option<(bool, String)> {
+        run_with_password("sudo", "-p", &sudo_preserve_env_args(python_path, full_args), password)
+    }
+
+    fn spawn_noninteractive(&self, python_path: &str, full_args: &[String]) -> Option<()> {
+        sudo_spawn_noninteractive(python_path, full_args)
+    }
+}
+
+/// `doas` uses `-n` like sudo, but its permission-denied wording is its own
+/// (`doas: Permission denied`), and it has no stdin-piped password mode we
+/// can safely drive non-interactively, so `try_interactive` is unsupported -
+/// a `doas`-only system without a passwordless rule falls through to pkexec.
+pub struct Doas;
+
+impl Escalator for Doas {
+    fn name(&self) -> &'static str {
+        "doas"
+    }
+
+    fn is_available(&self) -> bool {
+        binary_exists("doas")
+    }
+
+    fn try_noninteractive(&self, python_path: &str, full_args: &[String]) -> EscalationResult {
+        let mut args = vec!["-n".to_string()];
+        args.extend(env_wrapped(python_path, full_args));
+        let output = Command::new("doas").args(&args).output();
+
+        match output {
+            Ok(out) if out.status.success() => {
+                EscalationResult::Done(true, String::from_utf8_lossy(&out.stdout).trim().to_string())
+            }
+            Ok(out) => {
+                let err = String::from_utf8_lossy(&out.stderr).to_lowercase();
+                let needs_password = err.contains("authorization required") || err.contains("doas: not authenticated");
+
+                if needs_password {
+                    EscalationResult::NeedsPassword
+                } else {
+                    EscalationResult::Done(false, String::from_utf8_lossy(&out.stderr).trim().to_string())
+                }
+            }
+            Err(e) => EscalationResult::Done(false, e.to_string()),
+        }
+    }
+
+    fn spawn_noninteractive(&self, python_path: &str, full_args: &[String]) -> Option<()> {
+        let mut args = vec!["-n".to_string()];
+        args.extend(env_wrapped(python_path, full_args));
+        spawn_detached(Command::new("doas").args(&args).spawn().ok()?)
+    }
+}
+
+/// pkexec handles its own authentication dialog, so there's no
+/// non-interactive probe to make first - we always just run it and let the
+/// desktop's auth agent (and, if installed, our [`crate::polkit`] action) do
+/// the prompting.
+///
+/// Like the sudo backends (and unlike `doas`), pkexec's target here is
+/// `python_path` directly, not an `env -i`-wrapped command; unlike the sudo
+/// backends it passes no allow-list at all: pkexec already resets the environment to
+/// its own deterministic default before running the target program, and
+/// keeping the exec target as the interpreter (rather than a generic `env`)
+/// is what lets our polkit action cache authorization safely - see
+/// `crate::polkit`.
+pub struct Pkexec;
+
+impl Escalator for Pkexec {
+    fn name(&self) -> &'static str {
+        "pkexec"
+    }
+
+    fn is_available(&self) -> bool {
+        binary_exists("pkexec")
+    }
+
+    fn try_noninteractive(&self, python_path: &str, full_args: &[String]) -> EscalationResult {
+        let mut args = vec![python_path.to_string()];
+        args.extend(full_args.iter().cloned());
+        let output = Command::new("pkexec").args(&args).output();
+
+        match output {
+            Ok(out) if out.status.success() => {
+                EscalationResult::Done(true, String::from_utf8_lossy(&out.stdout).trim().to_string())
+            }
+            Ok(out) => EscalationResult::Done(false, String::from_utf8_lossy(&out.stderr).trim().to_string()),
+            Err(e) => EscalationResult::Done(false, e.to_string()),
+        }
+    }
+
+    fn spawn_noninteractive(&self, python_path: &str, full_args: &[String]) -> Option<()> {
+        let mut args = vec![python_path.to_string()];
+        args.extend(full_args.iter().cloned());
+        spawn_detached(Command::new("pkexec").args(&args).spawn().ok()?)
+    }
+}
+
+/// All backends in default priority order.
+pub fn all_backends() -> Vec<Box<dyn Escalator>> {
+    vec![Box::new(GnuSudo), Box::new(SudoRs), Box::new(Doas), Box::new(Pkexec)]
+}
+
+/// Available backends in priority order, with `pinned` (if set and present)
+/// moved to the front so it's tried first.
+pub fn ordered_backends(pinned: Option<&str>) -> Vec<Box<dyn Escalator>> {
+    let mut backends: Vec<Box<dyn Escalator>> = all_backends().into_iter().filter(|b| b.is_available()).collect();
+    if let Some(pinned) = pinned {
+        if let Some(idx) = backends.iter().position(|b| b.name() == pinned) {
+            let preferred = backends.remove(idx);
+            backends.insert(0, preferred);
+        }
+    }
+    backends
+}
@@ -1,11 +1,24 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
+mod animation;
+mod config;
+mod daemon;
+mod escalation;
+mod monitoring;
+mod polkit;
+mod theme;
+
+use animation::Animation;
+use config::{Config, Preset};
+use monitoring::RingBuffer;
+use theme::Theme;
 use eframe::egui;
 use poll_promise::Promise;
 use std::process::Command;
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
-fn hex_to_color(hex: &str) -> egui::Color32 {
+pub(crate) fn hex_to_color(hex: &str) -> egui::Color32 {
     let hex = hex.trim_start_matches('#');
     if hex.len() != 6 {
         return egui::Color32::WHITE;
@@ -21,6 +34,31 @@ fn color_to_hex(color: egui::Color32) -> String {
 }
 
 fn main() -> eframe::Result {
+    let cli_args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Invoked (by ourselves, escalated) to actually write the policy file.
+    if cli_args.first().map(String::as_str) == Some("--do-install-polkit-policy") {
+        let exec_path = cli_args.get(1).cloned().unwrap_or_default();
+        match polkit::install(&exec_path) {
+            Ok(()) => {
+                println!("Installed polkit action at {}", polkit::policy_path().display());
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Failed to install polkit action: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // User-facing entry point: escalate once to install the policy so later
+    // pkexec calls show a branded, cacheable prompt instead of the generic one.
+    if cli_args.first().map(String::as_str) == Some("--install-polkit-policy") {
+        let (ok, msg) = install_polkit_policy();
+        println!("{msg}");
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([720.0, 600.0])
@@ -45,6 +83,16 @@ enum Page {
     Fans,
 }
 
+impl Page {
+    fn from_flag(s: &str) -> Self {
+        match s {
+            "power" => Page::Power,
+            "fans" => Page::Fans,
+            _ => Page::Keyboard,
+        }
+    }
+}
+
 struct NekroApp {
     current_page: Page,
     status_msg: String,
@@ -80,24 +128,81 @@ struct NekroApp {
     fans_current: String,
 
     show_about: bool,
+    show_hotkeys: bool,
+
+    // UI theme
+    themes: Vec<Theme>,
+    current_theme: String,
+
+    // Custom animation engine
+    anim_running: Option<Animation>,
+    anim_speed: f32,
+    anim_base_color: String,
+    anim_secondary_color: String,
+    anim_phase: f32,
+    anim_last_tick: Option<Instant>,
+    anim_saved_colors: [String; 4],
+    anim_saved_brightness: i32,
+
+    // Persistent config / presets
+    config: Config,
+    selected_preset: String,
+    new_preset_name: String,
+
+    // Telemetry polling
+    poll_interval_secs: f32,
+    poll_history_len: usize,
+    last_poll: Option<Instant>,
+    fan_cpu_history: RingBuffer,
+    fan_gpu_history: RingBuffer,
+    battery_history: RingBuffer,
 
     // Async command handling
     command_queue: VecDeque<Vec<String>>,
     pending_command: Option<PendingCommand>,
+    password_request: Option<PendingPasswordRequest>,
+    password_input: String,
 }
 
 struct PendingCommand {
     args: Vec<String>,
     promise: Promise<(bool, String)>,
+    password_rx: std::sync::mpsc::Receiver<PasswordRequest>,
+}
+
+/// Sent by `run_privileged`'s background thread when a backend needs a
+/// password it couldn't get from a TTY (the normal case for a GUI app with
+/// no controlling terminal), so the UI thread can collect one instead.
+struct PasswordRequest {
+    backend: String,
+    reply: std::sync::mpsc::Sender<Option<String>>,
+}
+
+/// A `PasswordRequest` currently being shown to the user as a modal dialog.
+struct PendingPasswordRequest {
+    backend: String,
+    reply: std::sync::mpsc::Sender<Option<String>>,
 }
 
 impl NekroApp {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let config = Config::load();
+        let current_page = Page::from_flag(&config.flags.startup_page);
+        let themes = Theme::all();
+        let current_theme = config.flags.theme.clone();
+
+        if let Some(theme) = themes.iter().find(|t| t.name == current_theme) {
+            theme.apply(&cc.egui_ctx);
+        }
+
         let mut app = Self {
-            current_page: Page::Keyboard,
+            current_page,
             status_msg: "Ready".to_string(),
             is_error: false,
 
+            themes,
+            current_theme,
+
             kb_per_zone: true,
             kb_single_color: true,
             kb_colors: [
@@ -130,18 +235,184 @@ impl NekroApp {
             fans_current: "unknown".to_string(),
 
             show_about: false,
+            show_hotkeys: false,
+
+            anim_running: None,
+            anim_speed: 1.0,
+            anim_base_color: "00aaff".to_string(),
+            anim_secondary_color: "ff00aa".to_string(),
+            anim_phase: 0.0,
+            anim_last_tick: None,
+            anim_saved_colors: Default::default(),
+            anim_saved_brightness: 100,
+
+            selected_preset: config
+                .flags
+                .auto_apply_preset
+                .clone()
+                .unwrap_or_default(),
+            new_preset_name: String::new(),
+            config,
+
+            poll_interval_secs: 2.0,
+            poll_history_len: 60,
+            last_poll: None,
+            fan_cpu_history: RingBuffer::new(60),
+            fan_gpu_history: RingBuffer::new(60),
+            battery_history: RingBuffer::new(60),
 
             command_queue: VecDeque::new(),
             pending_command: None,
+            password_request: None,
+            password_input: String::new(),
         };
         app.refresh_all();
+        if let Some(name) = app.config.flags.auto_apply_preset.clone() {
+            app.load_preset(&name);
+        }
         app
     }
 
+    /// Build a `Preset` snapshot of the app's currently-applied settings.
+    fn preset_from_current(&self, name: String) -> Preset {
+        Preset {
+            name,
+            kb_colors: self.kb_colors.clone(),
+            kb_brightness: self.kb_brightness,
+            kb_effect_mode: self.kb_effect_mode.clone(),
+            logo_color: self.logo_color.clone(),
+            power_current: self.power_current.clone(),
+            fans_cpu_val: self.fans_cpu_val,
+            fans_gpu_val: self.fans_gpu_val,
+        }
+    }
+
+    fn save_preset(&mut self, name: String) {
+        if name.trim().is_empty() {
+            return;
+        }
+        let preset = self.preset_from_current(name.clone());
+        self.config.upsert_preset(preset);
+        if let Err(e) = self.config.save() {
+            self.status_error(format!("Failed to save config: {e}"));
+        } else {
+            self.selected_preset = name.clone();
+            self.status_info(format!("Saved preset '{name}'"));
+        }
+    }
+
+    fn delete_preset(&mut self, name: &str) {
+        self.config.remove_preset(name);
+        if let Err(e) = self.config.save() {
+            self.status_error(format!("Failed to save config: {e}"));
+        } else {
+            self.selected_preset.clear();
+            self.status_info(format!("Deleted preset '{name}'"));
+        }
+    }
+
+    /// Populate state from a preset and enqueue the commands to apply it.
+    fn load_preset(&mut self, name: &str) {
+        let Some(preset) = self.config.preset(name).cloned() else {
+            self.status_error(format!("No such preset '{name}'"));
+            return;
+        };
+
+        self.kb_colors = preset.kb_colors.clone();
+        self.kb_brightness = preset.kb_brightness;
+        self.kb_effect_mode = preset.kb_effect_mode.clone();
+        self.logo_color = preset.logo_color.clone();
+        self.fans_cpu_val = preset.fans_cpu_val;
+        self.fans_gpu_val = preset.fans_gpu_val;
+
+        let mut rgb_args = vec!["rgb".to_string(), "per-zone".to_string()];
+        rgb_args.extend(preset.kb_colors.iter().cloned());
+        rgb_args.push("-b".to_string());
+        rgb_args.push(preset.kb_brightness.to_string());
+        self.run_cmd(rgb_args);
+        self.run_cmd(vec!["rgb".to_string(), "per-zone-get".to_string()]);
+
+        self.run_cmd(vec![
+            "logo".to_string(),
+            "set".to_string(),
+            preset.logo_color.clone(),
+            "-b".to_string(),
+            self.logo_brightness.to_string(),
+            if self.logo_on { "--on".to_string() } else { "--off".to_string() },
+        ]);
+        self.run_cmd(vec!["logo".to_string(), "get".to_string()]);
+
+        if !preset.power_current.is_empty() {
+            self.run_cmd(vec!["power".to_string(), "set".to_string(), preset.power_current]);
+        }
+
+        self.run_cmd(vec![
+            "fan".to_string(),
+            "set".to_string(),
+            "--cpu".to_string(),
+            preset.fans_cpu_val.to_string(),
+            "--gpu".to_string(),
+            preset.fans_gpu_val.to_string(),
+        ]);
+        self.run_cmd(vec!["fan".to_string(), "get".to_string()]);
+
+        self.selected_preset = name.to_string();
+        self.status_info(format!("Loaded preset '{name}'"));
+    }
+
     fn run_cmd(&mut self, args: Vec<String>) {
         self.command_queue.push_back(args);
     }
 
+    /// Drain the command queue and rewrite it so each exact `(args[0],
+    /// args[1])` command survives only once, keeping the latest occurrence.
+    /// This collapses runs of identical read commands (`power get`, `fan
+    /// get`, ...) down to one entry and drops superseded mutations (e.g.
+    /// several slider-driven `fan set` calls) in favor of the last one
+    /// enqueued.
+    ///
+    /// Collapsing by key alone isn't enough to keep a read after the
+    /// mutation that's supposed to precede it: e.g. `[fan set A, fan get,
+    /// fan set B]` dedupes to just `fan get` and `fan set B`, but naively
+    /// keeping each survivor's own original position would run the get
+    /// first. So reads are additionally re-pinned right after the last
+    /// surviving mutation for their device, guaranteeing a `*get`/`list`
+    /// enqueued after a mutation still runs after it.
+    fn coalesce_queue(&mut self) {
+        if self.command_queue.len() <= 1 {
+            return;
+        }
+        let drained: Vec<Vec<String>> = self.command_queue.drain(..).collect();
+
+        let device = |args: &[String]| args.first().cloned().unwrap_or_default();
+        let verb = |args: &[String]| args.get(1).cloned().unwrap_or_default();
+        let is_read = |args: &[String]| {
+            let v = verb(args);
+            v == "get" || v == "list" || v.ends_with("-get")
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        let mut kept: Vec<Vec<String>> = Vec::with_capacity(drained.len());
+        for args in drained.into_iter().rev() {
+            if seen.insert((device(&args), verb(&args))) {
+                kept.push(args);
+            }
+        }
+        kept.reverse();
+
+        let (mutations, reads): (Vec<Vec<String>>, Vec<Vec<String>>) =
+            kept.into_iter().partition(|args| !is_read(args));
+        let mut result = mutations;
+        for read in reads {
+            let insert_at = result
+                .iter()
+                .rposition(|args| device(args) == device(&read))
+                .map_or(result.len(), |i| i + 1);
+            result.insert(insert_at, read);
+        }
+        self.command_queue.extend(result);
+    }
+
     fn refresh_all(&mut self) {
         self.run_cmd(vec!["power".to_string(), "list".to_string()]);
         self.run_cmd(vec!["power".to_string(), "get".to_string()]);
@@ -173,7 +444,10 @@ impl NekroApp {
             "battery" => {
                 if args.len() >= 2 {
                     match args[1].as_str() {
-                        "get" => self.battery_limit = output.trim() == "1",
+                        "get" => {
+                            self.battery_limit = output.trim() == "1";
+                            self.battery_history.push(if self.battery_limit { 100.0 } else { 0.0 });
+                        }
                         "on" | "off" | "set" => {
                             self.status_info(output);
                             self.run_cmd(vec!["battery".to_string(), "get".to_string()]);
@@ -207,6 +481,8 @@ impl NekroApp {
                         self.fans_gpu_auto = gpu == 0;
                         if cpu > 0 { self.fans_cpu_val = cpu; }
                         if gpu > 0 { self.fans_gpu_val = gpu; }
+                        self.fan_cpu_history.push(cpu as f32);
+                        self.fan_gpu_history.push(gpu as f32);
                     }
                 } else {
                     self.status_info(output);
@@ -268,6 +544,223 @@ impl NekroApp {
         }
     }
 
+    fn start_animation(&mut self, anim: Animation) {
+        // Only snapshot static state the first time an animation starts -
+        // switching between animations while one is already running must
+        // not overwrite it with a live animated frame, or a later Stop would
+        // restore that frame instead of the user's actual static state.
+        if self.anim_running.is_none() {
+            self.anim_saved_colors = self.kb_colors.clone();
+            self.anim_saved_brightness = self.kb_brightness;
+        }
+        self.anim_phase = 0.0;
+        self.anim_last_tick = Some(Instant::now());
+        self.anim_running = Some(anim);
+    }
+
+    fn stop_animation(&mut self) {
+        if self.anim_running.take().is_some() {
+            self.kb_colors = self.anim_saved_colors.clone();
+            self.kb_brightness = self.anim_saved_brightness;
+            let mut args = vec!["rgb".to_string(), "per-zone".to_string()];
+            args.extend(self.kb_colors.iter().cloned());
+            args.push("-b".to_string());
+            args.push(self.kb_brightness.to_string());
+            self.run_cmd(args);
+            self.run_cmd(vec!["rgb".to_string(), "per-zone-get".to_string()]);
+        }
+    }
+
+    /// Advance the running animation by one tick and, if the command queue is
+    /// idle, stream the computed frame to the keyboard. Called every
+    /// `update()`; skips a frame rather than backing up the queue.
+    fn tick_animation(&mut self, ctx: &egui::Context) {
+        let Some(anim) = self.anim_running else { return };
+
+        let interval = Duration::from_millis((1000.0 / self.anim_speed.max(0.1)) as u64);
+        let now = Instant::now();
+        let elapsed = self
+            .anim_last_tick
+            .map(|t| now.duration_since(t))
+            .unwrap_or(interval);
+
+        if elapsed >= interval {
+            self.anim_last_tick = Some(now);
+            self.anim_phase = (self.anim_phase + 0.02 * self.anim_speed).fract();
+
+            if self.pending_command.is_none() && self.command_queue.is_empty() {
+                let base = hex_to_color(&self.anim_base_color);
+                let secondary = hex_to_color(&self.anim_secondary_color);
+                let (zones, brightness) = anim.frame(
+                    self.anim_phase,
+                    (base.r(), base.g(), base.b()),
+                    (secondary.r(), secondary.g(), secondary.b()),
+                );
+                self.kb_colors = zones.clone();
+                self.kb_brightness = brightness;
+
+                let mut args = vec!["rgb".to_string(), "per-zone".to_string()];
+                args.extend(zones);
+                args.push("-b".to_string());
+                args.push(brightness.to_string());
+                self.run_cmd(args);
+            }
+        }
+
+        ctx.request_repaint_after(interval);
+    }
+
+    /// Poll fan/power/battery telemetry on `poll_interval_secs`, but only
+    /// when the previous sample has landed, so background polling never
+    /// starves a user-initiated "Apply" command.
+    fn tick_monitoring(&mut self, ctx: &egui::Context) {
+        let interval = Duration::from_secs_f32(self.poll_interval_secs.max(0.1));
+        let now = Instant::now();
+        let elapsed = self.last_poll.map(|t| now.duration_since(t)).unwrap_or(interval);
+
+        if elapsed >= interval {
+            self.last_poll = Some(now);
+            if self.pending_command.is_none() && self.command_queue.is_empty() {
+                self.run_cmd(vec!["fan".to_string(), "get".to_string()]);
+                self.run_cmd(vec!["power".to_string(), "get".to_string()]);
+                self.run_cmd(vec!["battery".to_string(), "get".to_string()]);
+            }
+        }
+
+        ctx.request_repaint_after(interval);
+    }
+
+    fn apply_kb_per_zone(&mut self) {
+        let mut args = vec!["rgb".to_string(), "per-zone".to_string()];
+        if self.kb_single_color {
+            args.push(self.kb_colors[0].clone());
+        } else {
+            for i in 0..4 {
+                args.push(self.kb_colors[i].clone());
+            }
+        }
+        args.push("-b".to_string());
+        args.push(self.kb_brightness.to_string());
+        self.run_cmd(args);
+        self.run_cmd(vec!["rgb".to_string(), "per-zone-get".to_string()]);
+    }
+
+    fn apply_kb_effect(&mut self) {
+        let mut args = vec![
+            "rgb".to_string(),
+            "effect".to_string(),
+            self.kb_effect_mode.clone(),
+            "-s".to_string(), self.kb_effect_speed.to_string(),
+            "-b".to_string(), self.kb_effect_brightness.to_string(),
+            "-d".to_string(), self.kb_effect_direction.to_string(),
+        ];
+        if !self.kb_effect_color.is_empty() {
+            args.push("-c".to_string());
+            args.push(self.kb_effect_color.clone());
+        }
+        self.run_cmd(args);
+        self.run_cmd(vec!["rgb".to_string(), "effect-get".to_string()]);
+    }
+
+    fn apply_fan_settings(&mut self) {
+        let mut args = vec!["fan".to_string()];
+        if self.fans_cpu_auto && (self.fans_link || self.fans_gpu_auto) {
+            args.push("auto".to_string());
+        } else {
+            args.push("set".to_string());
+            args.push("--cpu".to_string());
+            args.push(if self.fans_cpu_auto { "auto".to_string() } else { self.fans_cpu_val.to_string() });
+            args.push("--gpu".to_string());
+            let gpu_val = if self.fans_link {
+                if self.fans_cpu_auto { "auto".to_string() } else { self.fans_cpu_val.to_string() }
+            } else {
+                if self.fans_gpu_auto { "auto".to_string() } else { self.fans_gpu_val.to_string() }
+            };
+            args.push(gpu_val);
+        }
+        self.run_cmd(args);
+    }
+
+    /// Fire the current page's primary "Apply" action (bound to Enter).
+    fn apply_current_page(&mut self) {
+        match self.current_page {
+            Page::Keyboard => {
+                if self.kb_per_zone {
+                    self.apply_kb_per_zone();
+                } else {
+                    self.apply_kb_effect();
+                }
+            }
+            Page::Power => {}
+            Page::Fans => self.apply_fan_settings(),
+        }
+    }
+
+    /// Handle global keyboard shortcuts. Called once per frame from `update()`.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Don't fire bindings while a text field has focus - otherwise typing
+        // a preset name or hex color doubles as hotkey input (e.g. "1"/"2"/"3"
+        // switching pages, "R" refreshing, Enter applying the current page).
+        // Same while a modal window is open, so keys meant for it (or just
+        // dismissing it) don't also act on the page behind it.
+        if ctx.wants_keyboard_input() || self.show_about || self.show_hotkeys {
+            return;
+        }
+
+        let bindings = self.config.bindings.clone();
+
+        ctx.input(|input| {
+            if let Some(key) = config::key_from_name(&bindings.page_keyboard) {
+                if input.key_pressed(key) {
+                    self.current_page = Page::Keyboard;
+                }
+            }
+            if let Some(key) = config::key_from_name(&bindings.page_power) {
+                if input.key_pressed(key) {
+                    self.current_page = Page::Power;
+                }
+            }
+            if let Some(key) = config::key_from_name(&bindings.page_fans) {
+                if input.key_pressed(key) {
+                    self.current_page = Page::Fans;
+                }
+            }
+            if input.modifiers.ctrl && input.key_pressed(egui::Key::Tab) {
+                self.current_page = match self.current_page {
+                    Page::Keyboard => Page::Power,
+                    Page::Power => Page::Fans,
+                    Page::Fans => Page::Keyboard,
+                };
+            }
+            if let Some(key) = config::key_from_name(&bindings.refresh) {
+                if input.key_pressed(key) {
+                    self.refresh_all();
+                }
+            }
+            if let Some(key) = config::key_from_name(&bindings.apply) {
+                if input.key_pressed(key) {
+                    self.apply_current_page();
+                }
+            }
+        });
+    }
+
+    fn active_theme(&self) -> &Theme {
+        self.themes
+            .iter()
+            .find(|t| t.name == self.current_theme)
+            .unwrap_or(&self.themes[0])
+    }
+
+    fn apply_theme(&mut self, ctx: &egui::Context, name: String) {
+        if let Some(theme) = self.themes.iter().find(|t| t.name == name) {
+            theme.apply(ctx);
+        }
+        self.current_theme = name;
+        self.config.flags.theme = self.current_theme.clone();
+        let _ = self.config.save();
+    }
+
     fn status_info(&mut self, msg: impl Into<String>) {
         self.status_msg = msg.into();
         self.is_error = false;
@@ -277,10 +770,62 @@ impl NekroApp {
         self.status_msg = msg.into();
         self.is_error = true;
     }
+
+    /// Modal shown when a background escalation attempt needs a password it
+    /// couldn't collect from a TTY. The background thread is blocked on
+    /// `reply` until we send it one (or `None` on cancel).
+    fn ui_password_dialog(&mut self, ctx: &egui::Context) {
+        use zeroize::Zeroize;
+
+        let Some(request) = &self.password_request else { return };
+        let mut submit = false;
+        let mut cancel = false;
+
+        egui::Window::new(format!("{} password required", request.backend))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Enter your password to apply this change:");
+                let response = ui.add(egui::TextEdit::singleline(&mut self.password_input).password(true));
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    submit = true;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("OK").clicked() {
+                        submit = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if submit || cancel {
+            let request = self.password_request.take().unwrap();
+            let answer = if submit { Some(self.password_input.clone()) } else { None };
+            let _ = request.reply.send(answer);
+            self.password_input.zeroize();
+        }
+    }
 }
 
 impl eframe::App for NekroApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Pick up a password request from the background thread before
+        // checking whether the command itself is done - it won't be, since
+        // it's blocked waiting on this dialog.
+        if self.password_request.is_none() {
+            if let Some(pending) = &self.pending_command {
+                if let Ok(request) = pending.password_rx.try_recv() {
+                    self.password_request = Some(PendingPasswordRequest {
+                        backend: request.backend,
+                        reply: request.reply,
+                    });
+                }
+            }
+        }
+        self.ui_password_dialog(ctx);
+
         // Handle pending command
         if let Some(pending) = self.pending_command.take() {
             if let Some((ok, msg)) = pending.promise.ready() {
@@ -297,29 +842,64 @@ impl eframe::App for NekroApp {
 
         // Start next command if idle
         if self.pending_command.is_none() {
+            self.coalesce_queue();
             if let Some(args) = self.command_queue.pop_front() {
                 let args_clone = args.clone();
+                let pinned_backend = self.config.flags.escalation_backend.clone();
+                let (password_tx, password_rx) = std::sync::mpsc::channel();
                 self.pending_command = Some(PendingCommand {
                     args,
                     promise: Promise::spawn_thread("cmd", move || {
-                        run_privileged(args_clone)
+                        run_privileged(args_clone, pinned_backend, password_tx)
                     }),
+                    password_rx,
                 });
                 // Request a repaint to process the next command soon
                 ctx.request_repaint();
             }
         }
 
-        egui::TopBottomPanel::top("header").show(ctx, |ui| {
+        self.handle_shortcuts(ctx);
+        self.tick_animation(ctx);
+        self.tick_monitoring(ctx);
+
+        egui::TopBottomPanel::top("header")
+            .frame(egui::Frame::default().fill(self.active_theme().header).inner_margin(6.0))
+            .show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.current_page, Page::Keyboard, "Keyboard");
                 ui.selectable_value(&mut self.current_page, Page::Power, "Power");
                 ui.selectable_value(&mut self.current_page, Page::Fans, "Fans");
-                
+
+                ui.separator();
+                egui::ComboBox::from_id_salt("preset_picker")
+                    .selected_text(if self.selected_preset.is_empty() { "<preset>" } else { &self.selected_preset })
+                    .show_ui(ui, |ui| {
+                        for preset in &self.config.presets {
+                            ui.selectable_value(&mut self.selected_preset, preset.name.clone(), &preset.name);
+                        }
+                    });
+                if ui.button("Load").clicked() && !self.selected_preset.is_empty() {
+                    self.load_preset(&self.selected_preset.clone());
+                }
+                if ui.button("Delete").clicked() && !self.selected_preset.is_empty() {
+                    self.delete_preset(&self.selected_preset.clone());
+                }
+                ui.text_edit_singleline(&mut self.new_preset_name)
+                    .on_hover_text("New preset name");
+                if ui.button("Save").clicked() && !self.new_preset_name.trim().is_empty() {
+                    let name = self.new_preset_name.clone();
+                    self.save_preset(name);
+                    self.new_preset_name.clear();
+                }
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui.button("About").clicked() {
                         self.show_about = true;
                     }
+                    if ui.button("Hotkeys").clicked() {
+                        self.show_hotkeys = true;
+                    }
                     if ui.button("Refresh").clicked() {
                         self.refresh_all();
                     }
@@ -328,23 +908,104 @@ impl eframe::App for NekroApp {
         });
 
         if self.show_about {
+            let mut show_about = self.show_about;
+            let mut selected_theme = None;
             egui::Window::new("About Nekro Sense")
-                .open(&mut self.show_about)
+                .open(&mut show_about)
                 .show(ctx, |ui| {
                     ui.heading("Nekro Sense");
                     ui.label("Rust + egui GUI for Nekro-Sense.");
                     ui.label("Controls RGB, power profile, and fans via CLI helper.");
                     ui.hyperlink("https://github.com/FelipeFMA/nekro-sense");
+
+                    ui.separator();
+                    ui.label("Theme:");
+                    egui::ComboBox::from_id_salt("theme_picker")
+                        .selected_text(&self.current_theme)
+                        .show_ui(ui, |ui| {
+                            for theme in &self.themes {
+                                if ui.selectable_label(theme.name == self.current_theme, &theme.name).clicked() {
+                                    selected_theme = Some(theme.name.clone());
+                                }
+                            }
+                        });
+
+                    ui.separator();
+                    ui.label("Escalation backend:");
+                    let mut pinned = self.config.flags.escalation_backend.clone();
+                    egui::ComboBox::from_id_salt("escalation_backend_picker")
+                        .selected_text(pinned.as_deref().unwrap_or("Auto"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut pinned, None, "Auto");
+                            for backend in escalation::ordered_backends(None) {
+                                ui.selectable_value(&mut pinned, Some(backend.name().to_string()), backend.name());
+                            }
+                        });
+                    if pinned != self.config.flags.escalation_backend {
+                        self.config.flags.escalation_backend = pinned;
+                        let _ = self.config.save();
+                    }
+
+                    ui.separator();
+                    ui.label(format!(
+                        "Privileged helper: {}",
+                        if daemon::is_running() { "running" } else { "not running (escalates per call)" }
+                    ));
+                    if daemon::is_running() && ui.button("Stop helper").clicked() {
+                        daemon::shutdown();
+                    }
+
+                    ui.separator();
+                    let installed = polkit::is_installed();
+                    ui.label(format!(
+                        "Polkit action: {}",
+                        if installed { "installed (branded, cached prompt)" } else { "not installed (generic pkexec dialog)" }
+                    ));
+                    if !installed && ui.button("Install polkit action").clicked() {
+                        let (ok, msg) = install_polkit_policy();
+                        if ok {
+                            self.status_info(msg);
+                        } else {
+                            self.status_error(msg);
+                        }
+                    }
+                });
+            self.show_about = show_about;
+            if let Some(name) = selected_theme {
+                self.apply_theme(ctx, name);
+            }
+        }
+
+        if self.show_hotkeys {
+            let bindings = self.config.bindings.clone();
+            egui::Window::new("Hotkeys")
+                .open(&mut self.show_hotkeys)
+                .show(ctx, |ui| {
+                    egui::Grid::new("hotkey_grid").num_columns(2).show(ui, |ui| {
+                        ui.label("Switch to Keyboard page:"); ui.label(&bindings.page_keyboard); ui.end_row();
+                        ui.label("Switch to Power page:"); ui.label(&bindings.page_power); ui.end_row();
+                        ui.label("Switch to Fans page:"); ui.label(&bindings.page_fans); ui.end_row();
+                        ui.label("Cycle pages:"); ui.label("Ctrl+Tab"); ui.end_row();
+                        ui.label("Refresh:"); ui.label(&bindings.refresh); ui.end_row();
+                        ui.label("Apply current page:"); ui.label(&bindings.apply); ui.end_row();
+                    });
+                    ui.label("Bindings are configurable in the config file under [bindings].");
                 });
         }
 
         egui::TopBottomPanel::bottom("footer").show(ctx, |ui| {
+            let theme = self.active_theme();
             let color = if self.is_error {
-                egui::Color32::RED
+                theme.error
             } else {
-                ui.visuals().weak_text_color()
+                theme.weak_text
             };
-            ui.label(egui::RichText::new(&self.status_msg).color(color));
+            ui.horizontal(|ui| {
+                if !self.is_error {
+                    ui.colored_label(theme.success, "\u{2713}");
+                }
+                ui.label(egui::RichText::new(&self.status_msg).color(color));
+            });
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -391,18 +1052,7 @@ impl NekroApp {
                     }
                     ui.add(egui::Slider::new(&mut self.kb_brightness, 0..=100).text("Brightness"));
                     if ui.button("Apply").clicked() {
-                        let mut args = vec!["rgb".to_string(), "per-zone".to_string()];
-                        if self.kb_single_color {
-                            args.push(self.kb_colors[0].clone());
-                        } else {
-                            for i in 0..4 {
-                                args.push(self.kb_colors[i].clone());
-                            }
-                        }
-                        args.push("-b".to_string());
-                        args.push(self.kb_brightness.to_string());
-                        self.run_cmd(args);
-                        self.run_cmd(vec!["rgb".to_string(), "per-zone-get".to_string()]);
+                        self.apply_kb_per_zone();
                     }
                 });
             }
@@ -429,24 +1079,42 @@ impl NekroApp {
                         ui.text_edit_singleline(&mut self.kb_effect_color);
                     });
                     if ui.button("Apply").clicked() {
-                        let mut args = vec![
-                            "rgb".to_string(),
-                            "effect".to_string(),
-                            self.kb_effect_mode.clone(),
-                            "-s".to_string(), self.kb_effect_speed.to_string(),
-                            "-b".to_string(), self.kb_effect_brightness.to_string(),
-                            "-d".to_string(), self.kb_effect_direction.to_string(),
-                        ];
-                        if !self.kb_effect_color.is_empty() {
-                            args.push("-c".to_string());
-                            args.push(self.kb_effect_color.clone());
-                        }
-                        self.run_cmd(args);
-                        self.run_cmd(vec!["rgb".to_string(), "effect-get".to_string()]);
+                        self.apply_kb_effect();
                     }
                 });
             }
 
+            ui.separator();
+            ui.collapsing("Custom animation", |ui| {
+                egui::ComboBox::from_label("Animation")
+                    .selected_text(self.anim_running.unwrap_or(Animation::RainbowCycle).label())
+                    .show_ui(ui, |ui| {
+                        for anim in Animation::ALL {
+                            if ui.selectable_label(self.anim_running == Some(anim), anim.label()).clicked() {
+                                self.start_animation(anim);
+                            }
+                        }
+                    });
+                ui.add(egui::Slider::new(&mut self.anim_speed, 0.1..=5.0).text("Speed"));
+                ui.horizontal(|ui| {
+                    ui.label("Base color:");
+                    let mut color = hex_to_color(&self.anim_base_color);
+                    if ui.color_edit_button_srgba(&mut color).changed() {
+                        self.anim_base_color = color_to_hex(color);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Secondary color:");
+                    let mut color = hex_to_color(&self.anim_secondary_color);
+                    if ui.color_edit_button_srgba(&mut color).changed() {
+                        self.anim_secondary_color = color_to_hex(color);
+                    }
+                });
+                if ui.add_enabled(self.anim_running.is_some(), egui::Button::new("Stop")).clicked() {
+                    self.stop_animation();
+                }
+            });
+
             ui.separator();
             if ui.button("Turn Off Keyboard Backlight").clicked() {
                 self.run_cmd(vec![
@@ -500,7 +1168,7 @@ impl NekroApp {
         ui.group(|ui| {
             ui.horizontal(|ui| {
                 ui.label("Current profile:");
-                ui.colored_label(egui::Color32::LIGHT_BLUE, &self.power_current);
+                ui.colored_label(self.active_theme().accent, &self.power_current);
             });
             
             let mut selected_choice = None;
@@ -524,6 +1192,13 @@ impl NekroApp {
                 let cmd = if limit { "on" } else { "off" };
                 self.run_cmd(vec!["battery".to_string(), cmd.to_string()]);
             }
+
+            ui.separator();
+            ui.label(format!(
+                "Battery limit history ({})",
+                self.battery_history.latest().map_or("-".to_string(), |v| format!("{v:.0}%"))
+            ));
+            monitoring::sparkline(ui, &self.battery_history, 100.0, egui::Color32::GOLD);
         });
     }
 
@@ -553,33 +1228,95 @@ impl NekroApp {
             });
 
             if ui.button("Apply Fan Settings").clicked() {
-                let mut args = vec!["fan".to_string()];
-                if self.fans_cpu_auto && (self.fans_link || self.fans_gpu_auto) {
-                    args.push("auto".to_string());
-                } else {
-                    args.push("set".to_string());
-                    args.push("--cpu".to_string());
-                    args.push(if self.fans_cpu_auto { "auto".to_string() } else { self.fans_cpu_val.to_string() });
-                    args.push("--gpu".to_string());
-                    let gpu_val = if self.fans_link {
-                        if self.fans_cpu_auto { "auto".to_string() } else { self.fans_cpu_val.to_string() }
-                    } else {
-                        if self.fans_gpu_auto { "auto".to_string() } else { self.fans_gpu_val.to_string() }
-                    };
-                    args.push(gpu_val);
-                }
-                self.run_cmd(args);
+                self.apply_fan_settings();
             }
             
             ui.separator();
             ui.label(format!("Current values (CPU, GPU): {}", self.fans_current));
+
+            ui.separator();
+            ui.label(format!(
+                "CPU fan duty ({})",
+                self.fan_cpu_history.latest().map_or("-".to_string(), |v| format!("{v:.0}%"))
+            ));
+            monitoring::sparkline(ui, &self.fan_cpu_history, 100.0, egui::Color32::LIGHT_BLUE);
+            ui.label(format!(
+                "GPU fan duty ({})",
+                self.fan_gpu_history.latest().map_or("-".to_string(), |v| format!("{v:.0}%"))
+            ));
+            monitoring::sparkline(ui, &self.fan_gpu_history, 100.0, egui::Color32::LIGHT_GREEN);
+
+            ui.separator();
+            self.ui_polling_controls(ui);
+        });
+    }
+
+    fn ui_polling_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Poll every");
+            ui.add(egui::Slider::new(&mut self.poll_interval_secs, 0.5..=30.0).suffix("s"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("History length");
+            let mut len = self.poll_history_len;
+            if ui.add(egui::Slider::new(&mut len, 10..=300).text("samples")).changed() {
+                self.poll_history_len = len;
+                self.fan_cpu_history.set_capacity(len);
+                self.fan_gpu_history.set_capacity(len);
+                self.battery_history.set_capacity(len);
+            }
         });
     }
 }
 
-fn run_privileged(args: Vec<String>) -> (bool, String) {
-    let python_path = "python3";
-    
+/// Resolve an absolute interpreter path where possible, since polkit matches
+/// pkexec invocations to our action via the exact `exec.path` it was
+/// installed with rather than a bare `python3` lookup through `$PATH`.
+fn resolve_python_path() -> String {
+    for candidate in ["/usr/bin/python3", "/usr/local/bin/python3", "/bin/python3"] {
+        if std::path::Path::new(candidate).exists() {
+            return candidate.to_string();
+        }
+    }
+    "python3".to_string()
+}
+
+/// Escalate once (sudo, then pkexec) to re-invoke ourselves with
+/// `--do-install-polkit-policy` and write the action file. If this fails,
+/// returns the error and pkexec keeps showing its generic dialog.
+fn install_polkit_policy() -> (bool, String) {
+    let Ok(self_exe) = std::env::current_exe() else {
+        return (false, "could not locate our own executable".to_string());
+    };
+    let self_exe = self_exe.to_string_lossy().to_string();
+    let exec_path = resolve_python_path();
+
+    let output = Command::new("sudo")
+        .args(["-n", &self_exe, "--do-install-polkit-policy", &exec_path])
+        .output();
+    if let Ok(out) = &output {
+        if out.status.success() {
+            return (true, format!("Installed polkit action for {exec_path}"));
+        }
+    }
+
+    let output = Command::new("pkexec")
+        .args([&self_exe, "--do-install-polkit-policy", &exec_path])
+        .output();
+    match output {
+        Ok(out) if out.status.success() => (true, format!("Installed polkit action for {exec_path}")),
+        Ok(out) => (false, String::from_utf8_lossy(&out.stderr).trim().to_string()),
+        Err(e) => (false, e.to_string()),
+    }
+}
+
+fn run_privileged(
+    args: Vec<String>,
+    pinned_backend: Option<String>,
+    password_tx: std::sync::mpsc::Sender<PasswordRequest>,
+) -> (bool, String) {
+    let python_path = resolve_python_path();
+
     // Try to find nekroctl.py in the parent directory or current directory
     let script_path = if std::path::Path::new("../nekroctl.py").exists() {
         "../nekroctl.py"
@@ -593,8 +1330,8 @@ fn run_privileged(args: Vec<String>) -> (bool, String) {
     let mut full_args = vec![script_path.to_string()];
     full_args.extend(args);
 
-    // 1. Try normal
-    let output = Command::new(python_path)
+    // 1. Try normal, unprivileged
+    let output = Command::new(&python_path)
         .args(&full_args)
         .output();
 
@@ -604,7 +1341,7 @@ fn run_privileged(args: Vec<String>) -> (bool, String) {
         }
         Ok(out) => {
             let err = String::from_utf8_lossy(&out.stderr).to_lowercase();
-            let perm_denied = out.status.code() == Some(3) 
+            let perm_denied = out.status.code() == Some(3)
                 || err.contains("permission denied")
                 || err.contains("operation not permitted")
                 || err.contains("not authorized")
@@ -618,46 +1355,67 @@ fn run_privileged(args: Vec<String>) -> (bool, String) {
         Err(e) => return (false, e.to_string()),
     }
 
-    // 2. Try sudo -n
-    let mut sudo_args = vec!["-n".to_string(), python_path.to_string()];
-    sudo_args.extend(full_args.clone());
-    let output = Command::new("sudo")
-        .args(&sudo_args)
-        .output();
-
-    match output {
-        Ok(out) if out.status.success() => {
-            return (true, String::from_utf8_lossy(&out.stdout).trim().to_string());
-        }
-        Ok(out) => {
-            let err = String::from_utf8_lossy(&out.stderr).to_lowercase();
-            let sudo_requires_password = out.status.code() == Some(127)
-                || err.contains("a password is required")
-                || (err.contains("password") && (err.contains("authentication") || err.contains("is required")))
-                || err.contains("no tty present")
-                || err.contains("unable to authenticate");
-
-            if !sudo_requires_password {
-                return (false, String::from_utf8_lossy(&out.stderr).trim().to_string());
-            }
+    // 2. Prefer the long-lived privileged daemon over re-escalating from
+    // scratch: if it's already up, just ask it; if not, try to spawn it once
+    // and use it from here on. Only fall back to the one-shot path below if
+    // the daemon truly can't be started.
+    let request_args = &full_args[1..];
+    if let Some(result) = daemon::send_request(request_args) {
+        return result;
+    }
+    if daemon::spawn(&python_path, script_path, pinned_backend.as_deref()).is_ok() {
+        if let Some(result) = daemon::send_request(request_args) {
+            return result;
         }
-        Err(_) => {}
     }
 
-    // 3. Try pkexec
-    let mut pk_args = vec![python_path.to_string()];
-    pk_args.extend(full_args);
-    let output = Command::new("pkexec")
-        .args(&pk_args)
-        .output();
+    // 3. Escalate fresh for just this call, walking the available backends in
+    // priority order (the user's pinned backend, if any, goes first). sudo
+    // and sudo-rs pass a fixed environment allow-list via `--preserve-env`
+    // on top of their own default environment, while doas runs the call
+    // under `env -i` with only the allow-listed variables, so the privileged
+    // environment is not identical across these backends; pkexec is
+    // exempted from the allow-list since it
+    // already resets the environment deterministically on its own, which
+    // also keeps its exec target - and so our installed polkit action's
+    // `exec.path` match - specific to this interpreter rather than a generic
+    // `env` (see `escalation::ALLOWED_ENV_VARS` and `polkit.rs`).
+    let mut last_error = "no escalation backend available".to_string();
+    for backend in escalation::ordered_backends(pinned_backend.as_deref()) {
+        match backend.try_noninteractive(&python_path, &full_args) {
+            escalation::EscalationResult::Done(ok, msg) => {
+                if ok {
+                    return (true, msg);
+                }
+                last_error = msg;
+            }
+            escalation::EscalationResult::NeedsPassword => {
+                // Prefer a TTY prompt if we have one (this binary can still
+                // be launched from a terminal); otherwise ask the UI thread
+                // to collect the password through a dialog instead, and
+                // block until it replies.
+                let password = escalation::prompt_tty_password(backend.name()).or_else(|| {
+                    let (reply, reply_rx) = std::sync::mpsc::channel();
+                    password_tx
+                        .send(PasswordRequest { backend: backend.name().to_string(), reply })
+                        .ok()?;
+                    reply_rx.recv().ok().flatten()
+                });
 
-    match output {
-        Ok(out) if out.status.success() => {
-            (true, String::from_utf8_lossy(&out.stdout).trim().to_string())
-        }
-        Ok(out) => {
-            (false, String::from_utf8_lossy(&out.stderr).trim().to_string())
+                if let Some(mut password) = password {
+                    let result = backend.try_interactive(&python_path, &full_args, &password);
+                    {
+                        use zeroize::Zeroize;
+                        password.zeroize();
+                    }
+                    if let Some(result) = result {
+                        return result;
+                    }
+                }
+                last_error = format!("{} requires interactive authentication we can't provide", backend.name());
+            }
         }
-        Err(e) => (false, e.to_string()),
     }
+
+    (false, last_error)
 }
@@ -0,0 +1,78 @@
+//! Rolling history buffers for the telemetry poller, plus a minimal sparkline
+//! widget to render them.
+
+use eframe::egui;
+use std::collections::VecDeque;
+
+/// A ring buffer capped at a fixed sample count, used to track a metric over
+/// time (fan duty, battery limit, ...) for the sparkline plots.
+#[derive(Debug, Clone)]
+pub struct RingBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl RingBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, value: f32) {
+        if self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// Resize in place, trimming the oldest samples if the buffer shrinks.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<f32> {
+        self.samples.back().copied()
+    }
+}
+
+/// Draw a small sparkline of `history` scaled to `max_value`, at the current
+/// cursor position.
+pub fn sparkline(ui: &mut egui::Ui, history: &RingBuffer, max_value: f32, color: egui::Color32) {
+    let desired_size = egui::vec2(ui.available_width().min(200.0), 30.0);
+    let (rect, _response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+
+    let samples: Vec<f32> = history.iter().copied().collect();
+    if samples.len() < 2 {
+        ui.painter().text(
+            rect.left_center(),
+            egui::Align2::LEFT_CENTER,
+            "not enough data yet",
+            egui::FontId::default(),
+            ui.visuals().weak_text_color(),
+        );
+        return;
+    }
+
+    let max_value = max_value.max(1.0);
+    let step = rect.width() / (samples.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let x = rect.left() + i as f32 * step;
+            let y = rect.bottom() - (v.clamp(0.0, max_value) / max_value) * rect.height();
+            egui::pos2(x, y)
+        })
+        .collect();
+
+    ui.painter().add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+}
@@ -0,0 +1,60 @@
+//! Polkit action file for the pkexec escalation path.
+//!
+//! Without a registered action, pkexec shows a generic "run this program as
+//! root?" dialog on every call and can't cache authorization. Installing this
+//! action lets the desktop show a branded prompt for `dev.nekro-sense.apply`
+//! and remember it for a few minutes (`auth_admin_keep`).
+//!
+//! Caching here relies on `exec.path` staying specific to this app's own
+//! Python interpreter invocation: `escalation::Pkexec` is deliberately kept
+//! outside the `env -i` wrapping the `doas` backend uses (see
+//! `escalation::ALLOWED_ENV_VARS`), since `exec.path` matching is by program
+//! path alone - if the exec target were a generic binary like `env`, a cached
+//! approval would authorize *any* `pkexec env ...` call for the remainder of
+//! the cache window, not just this app's.
+
+use std::path::PathBuf;
+
+pub const ACTION_ID: &str = "dev.nekro-sense.apply";
+
+pub fn policy_path() -> PathBuf {
+    PathBuf::from("/usr/share/polkit-1/actions").join(format!("{ACTION_ID}.policy"))
+}
+
+/// Whether the action file is already installed, i.e. whether pkexec will
+/// show the branded prompt instead of its generic fallback dialog.
+pub fn is_installed() -> bool {
+    policy_path().exists()
+}
+
+/// Render the `.policy` XML. `exec_path` must be the absolute path to the
+/// interpreter that will actually be invoked, since polkit matches pkexec
+/// calls to this action via the `org.freedesktop.policykit.exec.path`
+/// annotation rather than an explicit action-id flag.
+pub fn policy_xml(exec_path: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE policyconfig PUBLIC "-//freedesktop//DTD PolicyKit Policy Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/PolicyKit/1/policyconfig.dtd">
+<policyconfig>
+  <action id="{ACTION_ID}">
+    <description>Apply Nekro Sense hardware settings</description>
+    <message>Authentication is required to change RGB, power, and fan settings</message>
+    <defaults>
+      <allow_any>auth_admin</allow_any>
+      <allow_inactive>auth_admin</allow_inactive>
+      <allow_active>auth_admin_keep</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">{exec_path}</annotate>
+  </action>
+</policyconfig>
+"#
+    )
+}
+
+/// Write the policy file. Requires root, since `/usr/share/polkit-1/actions`
+/// is not user-writable; callers should run this through the escalation
+/// helper rather than calling it directly from the unprivileged process.
+pub fn install(exec_path: &str) -> std::io::Result<()> {
+    std::fs::write(policy_path(), policy_xml(exec_path))
+}
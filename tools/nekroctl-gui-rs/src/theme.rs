@@ -0,0 +1,115 @@
+//! Selectable UI themes: a handful of built-ins plus `*.theme` palette files
+//! dropped into the themes directory (simple `key = "RRGGBB"` lines).
+
+use crate::hex_to_color;
+use eframe::egui;
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    pub background: egui::Color32,
+    pub accent: egui::Color32,
+    pub error: egui::Color32,
+    pub success: egui::Color32,
+    pub weak_text: egui::Color32,
+    pub header: egui::Color32,
+}
+
+impl Theme {
+    fn from_hex(
+        name: &str,
+        background: &str,
+        accent: &str,
+        error: &str,
+        success: &str,
+        weak_text: &str,
+        header: &str,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            background: hex_to_color(background),
+            accent: hex_to_color(accent),
+            error: hex_to_color(error),
+            success: hex_to_color(success),
+            weak_text: hex_to_color(weak_text),
+            header: hex_to_color(header),
+        }
+    }
+
+    /// Parse a `*.theme` file made of `key = "RRGGBB"` lines. Unknown or
+    /// missing keys fall back to the built-in Dark theme's slots.
+    fn from_file(name: &str, contents: &str) -> Self {
+        let fallback = Theme::builtins().remove(0);
+        let mut slots = std::collections::HashMap::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().trim_matches('"');
+                slots.insert(key.trim().to_string(), value.to_string());
+            }
+        }
+        let color = |key: &str, default: egui::Color32| {
+            slots.get(key).map(|hex| hex_to_color(hex)).unwrap_or(default)
+        };
+        Self {
+            name: name.to_string(),
+            background: color("background", fallback.background),
+            accent: color("accent", fallback.accent),
+            error: color("error", fallback.error),
+            success: color("success", fallback.success),
+            weak_text: color("weak_text", fallback.weak_text),
+            header: color("header", fallback.header),
+        }
+    }
+
+    /// Apply this theme's palette to the egui context's visuals.
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut visuals = if self.name == "Light" {
+            egui::Visuals::light()
+        } else {
+            egui::Visuals::dark()
+        };
+        visuals.panel_fill = self.background;
+        visuals.window_fill = self.background;
+        visuals.selection.bg_fill = self.accent;
+        visuals.hyperlink_color = self.accent;
+        visuals.warn_fg_color = self.error;
+        ctx.set_visuals(visuals);
+    }
+
+    pub fn builtins() -> Vec<Theme> {
+        vec![
+            Theme::from_hex("Dark", "1e1e1e", "00aaff", "ff0000", "00ff88", "a0a0a0", "2b2b2b"),
+            Theme::from_hex("Light", "f5f5f5", "0077cc", "cc0000", "008844", "606060", "e0e0e0"),
+            Theme::from_hex("Nekro", "12121a", "ff4fa0", "ff5555", "4fffb0", "8888aa", "1a1a26"),
+        ]
+    }
+
+    /// Enumerate built-in themes plus any `*.theme` file found in the themes
+    /// directory under the XDG config dir.
+    pub fn all() -> Vec<Theme> {
+        let mut themes = Theme::builtins();
+        if let Some(dir) = themes_dir() {
+            if let Ok(entries) = std::fs::read_dir(dir) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) != Some("theme") {
+                        continue;
+                    }
+                    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+                    if let Ok(contents) = std::fs::read_to_string(&path) {
+                        themes.push(Theme::from_file(stem, &contents));
+                    }
+                }
+            }
+        }
+        themes
+    }
+}
+
+fn themes_dir() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("nekro-sense").join("themes"))
+}